@@ -0,0 +1,89 @@
+//! Ensemble representation for data assimilation
+
+use ndarray::*;
+use ndarray_linalg::*;
+use rand::distributions::StandardNormal;
+use rand::Rng;
+
+use super::gaussian;
+use super::*;
+
+/// A collection of `m` state vectors of dimension `n`, stored as an `m`-by-`n`
+/// array with one member per row
+#[derive(Debug, Clone)]
+pub struct Ensemble {
+    states: Array2<R>,
+}
+
+impl Ensemble {
+    /// Wrap a raw `m`-by-`n` array of ensemble members
+    pub fn from_states(states: Array2<R>) -> Self {
+        Ensemble { states }
+    }
+
+    /// Dimension `n` of each ensemble member
+    pub fn dim(&self) -> usize {
+        self.states.cols()
+    }
+
+    /// Number of ensemble members `m`
+    pub fn size(&self) -> usize {
+        self.states.rows()
+    }
+
+    pub fn strides(&self) -> &[isize] {
+        self.states.strides()
+    }
+
+    /// Iterate over ensemble members
+    pub fn eiter(&self) -> impl Iterator<Item = ArrayView1<R>> {
+        self.states.outer_iter()
+    }
+
+    /// Iterate mutably over ensemble members
+    pub fn eiter_mut(&mut self) -> impl Iterator<Item = ArrayViewMut1<R>> {
+        self.states.outer_iter_mut()
+    }
+
+    pub fn states(&self) -> &Array2<R> {
+        &self.states
+    }
+
+    /// Draw `m` members from an isotropic Gaussian centered at `x0`
+    pub fn isotropic_gaussian(x0: &Array1<R>, m: usize, sigma: R) -> Self {
+        let n = x0.len();
+        let mut rng = rand::thread_rng();
+        let states = Array2::from_shape_fn((m, n), |(_, j)| x0[j] + sigma * rng.sample(StandardNormal));
+        Ensemble { states }
+    }
+
+    /// Sample mean and unbiased sample covariance of the ensemble
+    pub fn fit_gaussian(&self) -> gaussian::M {
+        let m = self.size() as R;
+        let mean = self.states.mean_axis(Axis(0)).expect("Ensemble is empty");
+        let anomaly = &self.states - &mean.broadcast(self.states.dim()).unwrap();
+        let cov = anomaly.t().dot(&anomaly) / (m - 1.0);
+        gaussian::M { center: mean, cov }
+    }
+
+    /// One analysis step of the stochastic Ensemble Kalman Filter: updates
+    /// every member in place given an observation operator `h`, observation
+    /// `y` and observation covariance `obs_cov`
+    pub fn analysis<Rn: Rng>(&mut self, h: &Array2<R>, y: &Array1<R>, obs_cov: &Array2<R>, rng: &mut Rn) {
+        let m = self.size();
+        let sqrt_m1 = ((m - 1) as R).sqrt();
+        let mean = self.states.mean_axis(Axis(0)).expect("Ensemble is empty");
+        let anomaly = (&self.states - &mean.broadcast(self.states.dim()).unwrap()) / sqrt_m1;
+        let h_anomaly = anomaly.dot(&h.t());
+        let innovation_cov = h_anomaly.t().dot(&h_anomaly) + obs_cov;
+        let gain = anomaly.t().dot(&h_anomaly).dot(&innovation_cov.invh().expect("Innovation covariance is singular"));
+        let obs_noise_l = obs_cov.cholesky(UPLO::Lower).expect("Observation covariance is not positive definite");
+        for mut member in self.states.outer_iter_mut() {
+            let eps = Array1::from_shape_fn(y.len(), |_| rng.sample(StandardNormal));
+            let perturbed_y = y + &obs_noise_l.dot(&eps);
+            let innovation = &perturbed_y - &h.dot(&member);
+            let update = gain.dot(&innovation);
+            member += &update;
+        }
+    }
+}
@@ -2,7 +2,11 @@
 
 use ndarray::*;
 use ndarray_linalg::*;
+use rand::distributions::StandardNormal;
+use rand::Rng;
+use rand_distr::{ChiSquared, Distribution};
 
+use super::ensemble::Ensemble;
 use super::*;
 
 /// m-parameter form of Gaussian
@@ -32,6 +36,45 @@ impl M {
         let ab = prec.dot(&self.center);
         E { ab, prec }
     }
+
+    /// Log-density at `x`; delegates to the cheaper e-parameter form
+    pub fn logpdf(&self, x: &Array1<R>) -> R {
+        self.to_e().logpdf(x)
+    }
+
+    /// Differential entropy of this Gaussian, in nats
+    pub fn entropy(&self) -> R {
+        let n = self.size() as R;
+        let l = self.cov.cholesky(UPLO::Lower).expect("Covariance matrix is not positive definite");
+        let log_det: R = 2.0 * l.diag().mapv(R::ln).sum();
+        0.5 * (n * (1.0 + (2.0 * ::std::f64::consts::PI as R).ln()) + log_det)
+    }
+
+    /// KL divergence `KL(self ‖ other)` between two Gaussians
+    pub fn kl_divergence(&self, other: &M) -> R {
+        let n = self.size() as R;
+        let l0 = self.cov.cholesky(UPLO::Lower).expect("Covariance matrix is not positive definite");
+        let l1 = other.cov.cholesky(UPLO::Lower).expect("Covariance matrix is not positive definite");
+        let log_det0: R = 2.0 * l0.diag().mapv(R::ln).sum();
+        let log_det1: R = 2.0 * l1.diag().mapv(R::ln).sum();
+        let other_prec = other.cov.invh().expect("Covariance matrix is singular");
+        let trace_term = other_prec.dot(&self.cov).diag().sum();
+        let diff = &other.center - &self.center;
+        let mahalanobis = diff.dot(&other_prec.dot(&diff));
+        0.5 * (trace_term + mahalanobis - n + (log_det1 - log_det0))
+    }
+
+    /// Draw an `Ensemble` of `m` members from this Gaussian via its Cholesky factor
+    pub fn sample<Rn: Rng>(&self, m: usize, rng: &mut Rn) -> Ensemble {
+        let n = self.size();
+        let l = self.cov.cholesky(UPLO::Lower).expect("Covariance matrix is not positive definite");
+        let mut states = Array2::zeros((m, n));
+        for mut row in states.outer_iter_mut() {
+            let z = Array1::from_shape_fn(n, |_| rng.sample(StandardNormal));
+            row.assign(&(&self.center + &l.dot(&z)));
+        }
+        Ensemble::from_states(states)
+    }
 }
 
 /// e-parameter form of Gaussian
@@ -55,11 +98,26 @@ impl E {
         self.ab.len()
     }
 
+    /// Log-density at `x`, computed directly from the precision form
+    pub fn logpdf(&self, x: &Array1<R>) -> R {
+        let n = self.size() as R;
+        let l = self.prec.cholesky(UPLO::Lower).expect("Precision matrix is not positive definite");
+        let log_det_prec: R = 2.0 * l.diag().mapv(R::ln).sum();
+        let center = self.prec.solveh(&self.ab).expect("Precision matrix is singular");
+        let quadratic = x.dot(&self.prec.dot(x)) - 2.0 * x.dot(&self.ab) + center.dot(&self.ab);
+        0.5 * log_det_prec - 0.5 * n * (2.0 * ::std::f64::consts::PI as R).ln() - 0.5 * quadratic
+    }
+
     pub fn to_m(&self) -> M {
         let cov = self.prec.invh().expect("Precision matrix is singular");
         let center = cov.dot(&self.ab);
         M { center, cov }
     }
+
+    /// Draw an `Ensemble` of `m` members from this Gaussian via the m-parameter form
+    pub fn sample<Rn: Rng>(&self, m: usize, rng: &mut Rn) -> Ensemble {
+        self.to_m().sample(m, rng)
+    }
 }
 
 impl<'a> ::std::ops::Mul<&'a E> for E {
@@ -101,3 +159,54 @@ impl From<M> for E {
         E { ab, prec }
     }
 }
+
+/// Gaussian prior over a state together with a linear observation operator
+/// projecting that state into observation space
+#[derive(Debug, Clone)]
+pub struct PGaussian {
+    /// Observation operator `H`
+    pub projection: Array2<R>,
+    /// Prior distribution over the state
+    pub prior: M,
+}
+
+impl PGaussian {
+    pub fn size(&self) -> usize {
+        self.prior.size()
+    }
+
+    /// Kalman analysis step: condition the prior on observation `y`
+    pub fn condition(&self, y: &Array1<R>, obs_noise: &Array2<R>) -> M {
+        let h = &self.projection;
+        let prior_e = self.prior.to_e();
+        let r_inv = obs_noise.invh().expect("Observation noise covariance is singular");
+        let ht_rinv = h.t().dot(&r_inv);
+        let prec = &prior_e.prec + &ht_rinv.dot(h);
+        let ab = &prior_e.ab + &ht_rinv.dot(y);
+        E { ab, prec }.to_m()
+    }
+}
+
+/// Draw a covariance matrix from a Wishart(`scale`, `df`) distribution via
+/// the Bartlett decomposition
+pub fn wishart_sample<Rn: Rng>(scale: &Array2<R>, df: usize, rng: &mut Rn) -> Array2<R> {
+    let n = scale.rows();
+    assert!(df >= n, "Invalid degrees of freedom: df must be >= scale matrix size");
+    let l = scale.cholesky(UPLO::Lower).expect("Scale matrix is not positive definite");
+    let mut a = Array2::<R>::zeros((n, n));
+    for i in 0..n {
+        let chi2 = ChiSquared::new((df - i) as R).expect("Invalid degrees of freedom");
+        a[[i, i]] = chi2.sample(rng).sqrt();
+        for j in 0..i {
+            a[[i, j]] = rng.sample(StandardNormal);
+        }
+    }
+    let la = l.dot(&a);
+    la.dot(&la.t())
+}
+
+/// Draw a covariance matrix from an inverse-Wishart(`scale`, `df`) distribution
+pub fn inverse_wishart_sample<Rn: Rng>(scale: &Array2<R>, df: usize, rng: &mut Rn) -> Array2<R> {
+    let w = wishart_sample(scale, df, rng);
+    w.invh().expect("Sampled Wishart matrix is singular")
+}
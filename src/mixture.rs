@@ -0,0 +1,98 @@
+//! Gaussian mixture models fit over ensemble data
+
+use ndarray::*;
+
+use super::ensemble::Ensemble;
+use super::gaussian;
+use super::gaussian::E;
+use super::*;
+
+/// A weighted mixture of Gaussians
+#[derive(Debug, Clone)]
+pub struct GaussianMixture {
+    /// Mixing weights, summing to one
+    pub weights: Array1<R>,
+    /// Gaussian components
+    pub components: Vec<gaussian::M>,
+}
+
+impl GaussianMixture {
+    /// Fit a `k`-component mixture to `ensemble` via EM, iterating until the
+    /// average log-likelihood changes by less than `tol` or `max_iter` is
+    /// reached
+    pub fn fit(ensemble: &Ensemble, k: usize, tol: R, max_iter: usize) -> Self {
+        let m = ensemble.size();
+        let n = ensemble.dim();
+        let init_cov = ensemble.fit_gaussian().cov;
+
+        let mut weights = Array1::from_elem(k, 1.0 / k as R);
+        let mut components: Vec<gaussian::M> = (0..k)
+            .map(|c| gaussian::M {
+                center: ensemble.states().row(c * m / k).to_owned(),
+                cov: init_cov.clone(),
+            })
+            .collect();
+
+        let mut prev_avg_ll = R::NEG_INFINITY;
+        for _ in 0..max_iter {
+            // E-step: responsibilities, computed in log-space for stability.
+            // Convert each component to its cheap precision form once per
+            // iteration instead of once per (member, component) pair.
+            let e_comps: Vec<E> = components.iter().map(|c| c.to_e()).collect();
+            let mut resp = Array2::<R>::zeros((m, k));
+            for (i, x) in ensemble.eiter().enumerate() {
+                let x = x.to_owned();
+                for c in 0..k {
+                    resp[[i, c]] = weights[c].ln() + e_comps[c].logpdf(&x);
+                }
+            }
+            let mut avg_ll = 0.0;
+            for mut row in resp.outer_iter_mut() {
+                let max = row.iter().cloned().fold(R::NEG_INFINITY, R::max);
+                let sum: R = row.iter().map(|&v| (v - max).exp()).sum();
+                avg_ll += (max + sum.ln()) / m as R;
+                row.mapv_inplace(|v| (v - max).exp() / sum);
+            }
+
+            // M-step
+            for c in 0..k {
+                let resp_c = resp.column(c);
+                let n_c = resp_c.sum();
+                if n_c < 1e-8 {
+                    // Responsibility collapsed to (near) zero: re-seed this
+                    // component from the overall ensemble statistics instead
+                    // of dividing by zero.
+                    weights[c] = 1e-8;
+                    components[c] = ensemble.fit_gaussian();
+                    continue;
+                }
+                weights[c] = n_c / m as R;
+
+                let mut center = Array1::<R>::zeros(n);
+                for (i, x) in ensemble.eiter().enumerate() {
+                    center += &(&x * resp_c[i]);
+                }
+                center /= n_c;
+
+                let mut cov = Array2::<R>::zeros((n, n));
+                for (i, x) in ensemble.eiter().enumerate() {
+                    let d = &x - &center;
+                    let outer = d.clone().insert_axis(Axis(1)).dot(&d.insert_axis(Axis(0)));
+                    cov += &(outer * resp_c[i]);
+                }
+                cov /= n_c;
+                for j in 0..n {
+                    cov[[j, j]] += 1e-6;
+                }
+                components[c] = gaussian::M { center, cov };
+            }
+
+            if (avg_ll - prev_avg_ll).abs() < tol {
+                break;
+            }
+            prev_avg_ll = avg_ll;
+        }
+
+        GaussianMixture { weights, components }
+    }
+}
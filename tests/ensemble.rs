@@ -1,6 +1,7 @@
 
 extern crate ndarray;
-extern crate data_assimilation as da;
+extern crate dars as da;
+extern crate rand;
 
 use ndarray::*;
 
@@ -28,3 +29,28 @@ fn ensemble_iter() {
         assert_eq!(v.len(), n);
     }
 }
+
+#[test]
+fn fit_gaussian_recovers_mean() {
+    let x0 = arr1(&[1.0, 2.0]);
+    let xs = da::ensemble::Ensemble::isotropic_gaussian(&x0, 5000, 1.0);
+    let fitted = xs.fit_gaussian();
+    assert!((fitted.center[0] - x0[0]).abs() < 0.1);
+    assert!((fitted.center[1] - x0[1]).abs() < 0.1);
+}
+
+#[test]
+fn analysis_moves_toward_observation() {
+    use rand::SeedableRng;
+
+    let x0 = arr1(&[1.0, 2.0]);
+    let mut xs = da::ensemble::Ensemble::isotropic_gaussian(&x0, 200, 1.0);
+    let h = Array::eye(2);
+    let y = arr1(&[10.0, 10.0]);
+    let obs_cov = 0.01 * Array::eye(2);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    xs.analysis(&h, &y, &obs_cov, &mut rng);
+    let fitted = xs.fit_gaussian();
+    assert!((fitted.center[0] - y[0]).abs() < 1.0);
+    assert!((fitted.center[1] - y[1]).abs() < 1.0);
+}
@@ -4,6 +4,7 @@ extern crate dars;
 extern crate ndarray;
 #[macro_use]
 extern crate ndarray_linalg;
+extern crate rand;
 
 use dars::gaussian::*;
 use dars::types::*;
@@ -23,7 +24,7 @@ mod e {
     use super::*;
 
     fn g2e() -> E {
-        Gaussian::from_mean(center(), cov()).into()
+        M { center: center(), cov: cov() }.into()
     }
 
     #[test]
@@ -39,48 +40,126 @@ mod e {
     }
 }
 
-mod gaussian {
+mod pgaussian {
     use super::*;
 
-    pub fn g() -> Gaussian {
-        Gaussian::from_mean(center(), cov())
+    fn pg_3to2() -> PGaussian {
+        let h = array![[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let prior = M { center: array![1.0, 0.0, 0.0], cov: Array::eye(3) };
+        PGaussian { projection: h, prior }
     }
 
     #[test]
-    fn merge() {
-        let g1 = g();
-        let g2 = g();
-        let mut g3 = &g1 * &g2;
-        println!("g3(E) = {:?}", &g3);
-        g3.as_m();
-        println!("g3(M) = {:?}", &g3);
-        assert_close_l2!(&g3.center(), &center(), 1e-7);
-        assert_close_l2!(&g3.cov(), &(0.5 * cov()), 1e-7);
+    fn size() {
+        let pg = pg_3to2();
+        assert_eq!(pg.size(), 3);
     }
 }
 
-mod pgaussian {
+mod sampling {
     use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
 
-    fn pg_3to2() -> PGaussian {
-        let h = array![[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
-        let g = gaussian::g();
-        PGaussian {
-            projection: h,
-            gaussian: g,
-        }
+    #[test]
+    fn m_sample_size() {
+        let m_dist = M { center: center(), cov: cov() };
+        let mut rng = StdRng::seed_from_u64(0);
+        let xs = m_dist.sample(100, &mut rng);
+        assert_eq!(xs.dim(), 2);
+        assert_eq!(xs.size(), 100);
     }
 
     #[test]
-    fn size() {
-        let pg = pg_3to2();
-        assert_eq!(pg.size(), 3);
+    fn e_sample_size() {
+        let e_dist = (M { center: center(), cov: cov() }).to_e();
+        let mut rng = StdRng::seed_from_u64(0);
+        let xs = e_dist.sample(100, &mut rng);
+        assert_eq!(xs.dim(), 2);
+        assert_eq!(xs.size(), 100);
+    }
+}
+
+mod logpdf {
+    use super::*;
+
+    #[test]
+    fn m_and_e_agree() {
+        let m_dist = M { center: center(), cov: cov() };
+        let e_dist = m_dist.to_e();
+        let x = array![0.5, -0.5];
+        assert_close_l2!(&array![m_dist.logpdf(&x)], &array![e_dist.logpdf(&x)], 1e-7);
+    }
+
+    #[test]
+    fn peak_at_center() {
+        let m_dist = M { center: center(), cov: cov() };
+        let off = array![3.0, 3.0];
+        assert!(m_dist.logpdf(&center()) > m_dist.logpdf(&off));
+    }
+}
+
+mod divergence {
+    use super::*;
+
+    #[test]
+    fn kl_self_is_zero() {
+        let m_dist = M { center: center(), cov: cov() };
+        assert!(m_dist.kl_divergence(&m_dist).abs() < 1e-7);
     }
 
-    #[should_panic]
     #[test]
-    fn upward_reduction() {
-        pg_3to2().reduction();
+    fn kl_between_distinct_gaussians() {
+        // p = N([0, 0], I), q = N([1, 0], 2I); hand-computed reference:
+        // KL(p||q) = 0.5*(tr(0.5I) + 0.5 - 2 + log(4/1)) = 0.5*(1 + 0.5 - 2 + ln4)
+        let p = M { center: array![0.0, 0.0], cov: Array::eye(2) };
+        let q = M { center: array![1.0, 0.0], cov: 2.0 * Array::eye(2) };
+        let expected = 0.5 * (1.0 + 0.5 - 2.0 + 4.0_f64.ln());
+        assert!((p.kl_divergence(&q) - expected).abs() < 1e-7);
     }
 
+    #[test]
+    fn entropy_of_standard_normal() {
+        let m_dist = M { center: center(), cov: cov() };
+        let expected = 0.5 * (2.0 * (1.0 + (2.0 * ::std::f64::consts::PI).ln()));
+        assert!((m_dist.entropy() - expected).abs() < 1e-7);
+    }
+}
+
+mod wishart {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn sample_is_symmetric_positive_definite() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let w = wishart_sample(&cov(), 5, &mut rng);
+        assert_close_l2!(&w, &w.t().to_owned(), 1e-10);
+        assert!(w.cholesky(UPLO::Lower).is_ok());
+    }
+
+    #[test]
+    fn inverse_wishart_inverts_wishart() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let iw = inverse_wishart_sample(&cov(), 5, &mut rng);
+        assert!(iw.cholesky(UPLO::Lower).is_ok());
+        // Same seed, so this is the Wishart draw that `iw` is the inverse of
+        let w = wishart_sample(&cov(), 5, &mut StdRng::seed_from_u64(0));
+        assert_close_l2!(&iw.dot(&w), &Array2::<R>::eye(2), 1e-7);
+    }
+}
+
+mod condition {
+    use super::*;
+
+    #[test]
+    fn moves_toward_observation() {
+        let prior = M { center: center(), cov: cov() };
+        let pg = PGaussian { projection: Array::eye(2), prior };
+        let y = array![5.0, 5.0];
+        let obs_noise = 0.01 * Array::eye(2);
+        let posterior = pg.condition(&y, &obs_noise);
+        assert_close_l2!(&posterior.center, &y, 1e-1);
+    }
 }
@@ -0,0 +1,25 @@
+extern crate dars;
+extern crate ndarray;
+
+use dars::ensemble::Ensemble;
+use dars::mixture::GaussianMixture;
+use dars::types::*;
+
+use ndarray::*;
+
+#[test]
+fn fit_recovers_two_modes() {
+    let a = Ensemble::isotropic_gaussian(&arr1(&[-5.0, 0.0]), 200, 0.5);
+    let b = Ensemble::isotropic_gaussian(&arr1(&[5.0, 0.0]), 200, 0.5);
+    let states = stack![Axis(0), *a.states(), *b.states()];
+    let ensemble = Ensemble::from_states(states);
+
+    let mixture = GaussianMixture::fit(&ensemble, 2, 1e-6, 100);
+
+    assert_eq!(mixture.components.len(), 2);
+    let centers: Vec<R> = mixture.components.iter().map(|c| c.center[0]).collect();
+    let min = centers.iter().cloned().fold(R::INFINITY, R::min);
+    let max = centers.iter().cloned().fold(R::NEG_INFINITY, R::max);
+    assert!((min - (-5.0)).abs() < 1.0);
+    assert!((max - 5.0).abs() < 1.0);
+}